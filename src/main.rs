@@ -6,24 +6,42 @@
 mod resp {
 
 	#[derive(PartialEq, Debug)]
-	pub enum RespType<'a> {
+	pub enum RespType {
 
 		// Simple strings are encoded as a plus (+) character, followed by a string. The string mustn't
 		// contain a CR (\r) or LF (\n) character and is terminated by CRLF (i.e., \r\n).
-		SimpleString(&'a str),
+		SimpleString(String),
 
 		// A bulk string represents a single binary string.
 		// Read more here - https://redis.io/docs/reference/protocol-spec/#bulk-strings.
 		// Format of data it holds - One or more decimal digits (0..9) as the string's length, in bytes,
 		// as an unsigned, base-10 value..
-		BulkString(&'a str),
+		BulkString(String),
 
 		// Represents an array of RESP encoded elements.
 		// Read more here - https://redis.io/docs/reference/protocol-spec/#arrays.
-		Array(Vec<Self>)
+		Array(Vec<Self>),
+
+		// Represents the absence of a value, e.g. a 'GET' on a key that doesn't exist. Encoded as a
+		// null bulk string ('$-1\r\n').
+		// Read more here - https://redis.io/docs/reference/protocol-spec/#null-elements-in-arrays-and-bulk-strings.
+		Null,
+
+		// Represents the absence of an array, as opposed to an empty array. Encoded as a null array
+		// ('*-1\r\n').
+		// Read more here - https://redis.io/docs/reference/protocol-spec/#null-elements-in-arrays-and-bulk-strings.
+		NullArray,
+
+		// Errors are encoded as a minus (-) character, followed by a message describing the error.
+		// Read more here - https://redis.io/docs/reference/protocol-spec/#simple-errors.
+		Error(String),
+
+		// Integers are encoded as a colon (:) character, followed by the number as a base-10 string.
+		// Read more here - https://redis.io/docs/reference/protocol-spec/#integers.
+		Integer(i64)
 	}
 
-	pub fn encode<'respType, 's>(respType: RespType<'respType>) -> String {
+	pub fn encode(respType: RespType) -> String {
 		match respType {
 
 			RespType::SimpleString(s) => format!("+{}\r\n", s),
@@ -37,115 +55,441 @@ mod resp {
 					result.push_str(&encode(item));}
 
 				result
-			}
+			},
+
+			RespType::Null => String::from("$-1\r\n"),
+
+			RespType::NullArray => String::from("*-1\r\n"),
+
+			RespType::Error(message) => format!("-{}\r\n", message),
+
+			RespType::Integer(i) => format!(":{}\r\n", i)
 		}
 	}
 
-	// decode takes in a RESP encoded string, parses it and returns the result. The RESP encoded string
-	// can contain a single element or multiple elements concatenated together.
-	pub fn decode<'a>(s: &'_ str) -> (RespType, &'_ str) {
-		let firstcharacter= s.chars( ).next( ).unwrap( );
+	// DecodeError is why 'decode' couldn't produce a complete 'RespType' from the given buffer.
+	#[derive(PartialEq, Debug)]
+	pub enum DecodeError {
+		// Incomplete means the buffer doesn't yet hold a full frame - e.g. the terminating CRLF
+		// hasn't arrived, or a bulk string's declared length exceeds the buffered data. The caller
+		// should read more bytes and retry with the same (plus newly read) buffer.
+		Incomplete,
+
+		// Malformed means the buffer holds bytes that can never form a valid RESP frame.
+		Malformed(String)
+	}
+
+	// decode takes in a RESP encoded string, parses out a single element and returns it along with
+	// whatever of the input wasn't consumed by that element. The input can be a single frame, a
+	// partial frame, or multiple frames concatenated together - only one is decoded per call.
+	pub fn decode<'a>(s: &'a str) -> Result<(RespType, &'a str), DecodeError> {
+		let firstcharacter= match s.chars( ).next( ) {
+			Some(firstcharacter) => firstcharacter,
+			None => return Err(DecodeError::Incomplete)
+		};
+
 		match firstcharacter {
 
 			'+' => {
 				let s= s.strip_prefix('+').unwrap( );
 
-				let (decodedValue, remaining)= s.split_once("\r\n").unwrap( );
+				let (decodedValue, remaining)= s.split_once("\r\n")
+					.ok_or(DecodeError::Incomplete)?;
 
-				(RespType::SimpleString(decodedValue), remaining)
+				Ok((RespType::SimpleString(decodedValue.to_string( )), remaining))
 			},
 
-			'$' => {
-				let (length, remaining)= s.strip_prefix('$').unwrap( )
-													.split_once("\r\n").unwrap( );
+			'-' => {
+				let s= s.strip_prefix('-').unwrap( );
+
+				let (message, remaining)= s.split_once("\r\n")
+					.ok_or(DecodeError::Incomplete)?;
+
+				Ok((RespType::Error(message.to_string( )), remaining))
+			},
+
+			':' => {
+				let s= s.strip_prefix(':').unwrap( );
+
+				let (i, remaining)= s.split_once("\r\n")
+					.ok_or(DecodeError::Incomplete)?;
 
-				let (s, remaining)= remaining.split_once("\r\n").unwrap( );
+				let i= i.parse::<i64>( )
+					.map_err(|_| DecodeError::Malformed(format!("invalid integer '{}'", i)))?;
 
-				let length= length.parse::<usize>( ).unwrap( );
-				if s.len( ) != length {
-					panic!( )}
+				Ok((RespType::Integer(i), remaining))
+			},
 
-				(RespType::BulkString(s), remaining)
+			'$' => {
+				let (length, remaining)= s.strip_prefix('$').unwrap( )
+					.split_once("\r\n").ok_or(DecodeError::Incomplete)?;
+
+				// A length of -1 denotes a null bulk string, which has no data or trailing CRLF of its
+				// own.
+				let length= length.parse::<i64>( )
+					.map_err(|_| DecodeError::Malformed(format!("invalid bulk string length '{}'", length)))?;
+				if length == -1 {
+					return Ok((RespType::Null, remaining))}
+
+				let length= usize::try_from(length)
+					.map_err(|_| DecodeError::Malformed(format!("invalid bulk string length '{}'", length)))?;
+				if remaining.len( ) < length + 2 {
+					return Err(DecodeError::Incomplete)}
+
+				// Anything other than CRLF right where the declared length ends (including a byte in the
+				// middle of a UTF-8 character) means the length doesn't match the data.
+				if !remaining.is_char_boundary(length) {
+					return Err(DecodeError::Malformed(format!("bulk string doesn't match its declared length {}", length)))}
+
+				let (s, remaining)= remaining.split_at(length);
+				let remaining= remaining.strip_prefix("\r\n")
+					.ok_or_else(|| DecodeError::Malformed(format!("bulk string doesn't match its declared length {}", length)))?;
+
+				Ok((RespType::BulkString(s.to_string( )), remaining))
 			},
 
 			'*' => {
 				let (length, mut data)= s.strip_prefix('*').unwrap( )
-																 .split_once("\r\n").unwrap( );
+					.split_once("\r\n").ok_or(DecodeError::Incomplete)?;
+
+				// A length of -1 denotes a null array, as opposed to an empty one.
+				let signedLength= length.parse::<i64>( )
+					.map_err(|_| DecodeError::Malformed(format!("invalid array length '{}'", length)))?;
+				if signedLength == -1 {
+					return Ok((RespType::NullArray, data))}
+
+				let length= usize::try_from(signedLength)
+					.map_err(|_| DecodeError::Malformed(format!("invalid array length '{}'", length)))?;
+
+				// An array element takes at least 3 bytes to encode (e.g. '+\r\n'), so a declared
+				// length greater than the number of buffered bytes is either incomplete or lying about
+				// its size - either way, 'with_capacity' must not trust it outright.
+				if length > data.len( ) {
+					return Err(DecodeError::Incomplete)}
 
-				let length= length.parse::<usize>( ).unwrap( );
 				let mut vector= Vec::<RespType>::with_capacity(length);
 
 				for _ in 0..length {
-					let (respType, remaining)= decode(data);
+					let (respType, remaining)= decode(data)?;
 
 					data= remaining;
 					vector.push(respType);
 				}
 
 				let remaining= data;
-				(RespType::Array(vector), remaining)
+				Ok((RespType::Array(vector), remaining))
 			},
 
-			_ => panic!( )
+			_ => Err(DecodeError::Malformed(format!("unexpected leading byte '{}'", firstcharacter)))
+		}
+	}
+}
+
+// ConnectionAddr models where a Redis server can be reached, and parseConnectionInfo turns the
+// address strings accepted on the command line (and by 'Client::new') into one.
+mod connection {
+	use std::path::PathBuf;
+
+	// ConnectionAddr is where a Redis server can be reached - either a TCP host/port pair or a Unix
+	// domain socket path.
+	#[derive(Debug, Clone, PartialEq)]
+	pub enum ConnectionAddr {
+		Tcp(String, u16),
+		Unix(PathBuf)
+	}
+
+	const DEFAULT_PORT: u16 = 6379;
+
+	// parseConnectionInfo parses a Redis connection address into a 'ConnectionAddr'. It accepts
+	// 'redis://host:port' (defaulting the port to 6379 when omitted), 'redis+unix:///path/to.sock',
+	// and a plain 'host:port' for callers that don't use a URL.
+	pub fn parseConnectionInfo(address: &str) -> ConnectionAddr {
+		if let Some(path)= address.strip_prefix("redis+unix://") {
+			return ConnectionAddr::Unix(PathBuf::from(path))}
+
+		let hostAndPort= address.strip_prefix("redis://").unwrap_or(address);
+
+		match hostAndPort.split_once(':') {
+			Some((host, port)) => {
+				let port= port.parse::<u16>( )
+					.unwrap_or_else(|_| panic!("ERROR: invalid port '{}'", port));
+
+				ConnectionAddr::Tcp(host.to_string( ), port)
+			},
+
+			None => ConnectionAddr::Tcp(hostAndPort.to_string( ), DEFAULT_PORT)
 		}
 	}
 }
 
 mod server {
-	use std::{net::TcpListener, io::{prelude::*, BufReader}, thread::spawn};
+	use std::{
+		collections::{HashMap, HashSet},
+		net::{TcpListener, TcpStream},
+		io::{self, prelude::*},
+		os::unix::net::{UnixListener, UnixStream},
+		str,
+		sync::{mpsc::{self, Sender}, Arc, Mutex},
+		thread::spawn,
+		time::{Duration, Instant}
+	};
+	use crate::connection::{self, ConnectionAddr};
 	use crate::resp::{self, RespType};
 
-	fn pingHandler<'a>(arg: &'_ str) -> RespType<'_> {
-		RespType::SimpleString(arg)
+	fn pingHandler(arg: &str) -> RespType {
+		RespType::SimpleString(arg.to_string( ))
 	}
 
-	pub struct TcpServer;
+	// Entry is a single value held by the 'Store', along with an optional expiry deadline.
+	struct Entry {
+		value: String,
+		expiresAt: Option<Instant>
+	}
 
-	impl TcpServer {
-		// new starts a multi-threaded TCP server.
-		pub fn new( ) {
-			println!("INFO: 🚀 Starting TCP server");
+	// Store is the in-memory key-value store backing the 'SET'/'GET' commands. It's shared across
+	// connection threads behind an 'Arc', with the map itself guarded by a 'Mutex'. Expiry is lazy:
+	// an entry past its deadline is only evicted the next time it's looked up.
+	struct Store {
+		entries: Mutex<HashMap<String, Entry>>
+	}
+
+	impl Store {
+		fn new( ) -> Self {
+			Store { entries: Mutex::new(HashMap::new( )) }
+		}
 
-			let tcpLister= TcpListener::bind("127.0.0.1:6379")
-				.expect("ERROR: binding the TCP server to given socket address");
+		// set inserts 'key' with 'value'. 'expiresIn', if given, sets a deadline after which the entry
+		// is treated as absent. 'onlyIfAbsent' ('NX') skips the insert if the key already holds a live
+		// value; 'onlyIfPresent' ('XX') skips it unless the key already holds a live value. Returns
+		// whether the value was actually set.
+		fn set(&self, key: &str, value: &str, expiresIn: Option<Duration>, onlyIfAbsent: bool, onlyIfPresent: bool) -> bool {
+			let mut entries= self.entries.lock( ).unwrap( );
 
-			for stream in tcpLister.incoming( ) {
-				let mut stream= stream.expect("ERROR: parsing incoming connection stream");
+			let exists= entries.get(key).is_some_and(|entry| !Self::isExpired(entry));
+			if (onlyIfAbsent && exists) || (onlyIfPresent && !exists) {
+				return false}
 
-				spawn(move | | {
-					let mut bufferReader= BufReader::new(&stream);
+			let expiresAt= expiresIn.map(|duration| Instant::now( ) + duration);
+			entries.insert(key.to_string( ), Entry { value: value.to_string( ), expiresAt });
 
-					let mut request= String::new( );
-					bufferReader.read_to_string(&mut request)
-											.expect("ERROR: parsing incoming connection stream");
+			true
+		}
+
+		// get returns the live value stored at 'key', or 'None' if it's absent or has expired. An
+		// expired entry is evicted as a side effect.
+		fn get(&self, key: &str) -> Option<String> {
+			let mut entries= self.entries.lock( ).unwrap( );
+
+			match entries.get(key) {
+				Some(entry) if Self::isExpired(entry) => {
+					entries.remove(key);
+					None
+				},
 
-					let response= Self::handleRequest(&request);
+				Some(entry) => Some(entry.value.clone( )),
 
-					stream.write_all(response.as_bytes( )).unwrap( );
-				});
+				None => None
 			}
 		}
 
-		// handleRequest takes in a request, parses it to a Redis command, executes that command and
-		// returns RESP encoded execution result.
-		pub fn handleRequest<'a>(request: &str) -> String {
-			// Redis generally uses RESP as a request-response protocol in the following way :
-			//
-			// 1. Clients send commands to a Redis server as an array of bulk strings. The first (and
-			//		sometimes also the second) bulk string in the array is the command's name. Subsequent
-			// 		elements of the array are the arguments for the command.
-			//
-			// 2. The server replies with a RESP type.
+		fn isExpired(entry: &Entry) -> bool {
+			entry.expiresAt.is_some_and(|expiresAt| Instant::now( ) >= expiresAt)
+		}
+	}
+
+	// SubscriberRegistry maps a channel name to the senders of every connection currently
+	// subscribed to it, so 'PUBLISH' can push a message to each of them. It's shared across
+	// connection threads the same way 'Store' is.
+	struct SubscriberRegistry {
+		channels: HashMap<String, Vec<Sender<String>>>
+	}
 
-			let firstCharacter= request.chars( ).next( ).unwrap( );
-			if firstCharacter != '*' {
-				panic!( )}
+	impl SubscriberRegistry {
+		fn new( ) -> Self {
+			SubscriberRegistry { channels: HashMap::new( ) }
+		}
 
-			let (decodedRequest, _)= resp::decode(&request);
+		fn subscribe(&mut self, channel: &str, sender: Sender<String>) {
+			self.channels.entry(channel.to_string( )).or_default( ).push(sender);
+		}
 
-			let decodedResponse: RespType= match decodedRequest {
+		// publish hands the RESP encoded 'message' push to every subscriber of 'channel' and returns
+		// how many senders accepted it. A sender fails to send only once its connection's writer
+		// thread has exited (after a write to the disconnected socket failed); such a sender is
+		// pruned from the registry.
+		fn publish(&mut self, channel: &str, message: String) -> i64 {
+			let Some(senders)= self.channels.get_mut(channel) else {
+				return 0};
+
+			senders.retain(|sender| sender.send(message.clone( )).is_ok( ));
+
+			senders.len( ) as i64
+		}
+	}
+
+	// BUFFER_SIZE is the size of the fixed, reused read buffer backing each connection - two 4 KiB
+	// pages, which comfortably covers the RESP frames this server expects without reallocating.
+	const BUFFER_SIZE: usize = 8 * 1024;
+
+	// ConnectionStream is a duplex byte stream that can be split into an owned reading half and an
+	// owned writing half, so a connection's reads and pushed pub/sub messages can be handled by
+	// separate pieces of code without fighting over a shared '&mut'.
+	trait ConnectionStream: Read + Write + Send + 'static {
+		fn tryClone(&self) -> io::Result<Self> where Self: Sized;
+	}
+
+	impl ConnectionStream for TcpStream {
+		fn tryClone(&self) -> io::Result<Self> {
+			self.try_clone( )
+		}
+	}
+
+	impl ConnectionStream for UnixStream {
+		fn tryClone(&self) -> io::Result<Self> {
+			self.try_clone( )
+		}
+	}
+
+	pub struct TcpServer;
+
+	impl TcpServer {
+		// new starts a multi-threaded Redis server, listening on 'address' (see
+		// 'connection::parseConnectionInfo' for the accepted forms). A TCP address binds a
+		// 'TcpListener'; a Unix domain socket address binds a 'UnixListener'.
+		pub fn new(address: &str) {
+			println!("INFO: 🚀 Starting Redis server");
+
+			let store= Arc::new(Store::new( ));
+			let subscribers= Arc::new(Mutex::new(SubscriberRegistry::new( )));
+
+			match connection::parseConnectionInfo(address) {
+				ConnectionAddr::Tcp(host, port) => {
+					let listener= TcpListener::bind((host.as_str( ), port))
+						.expect("ERROR: binding the TCP server to given socket address");
+
+					for stream in listener.incoming( ) {
+						let stream= stream.expect("ERROR: parsing incoming connection stream");
+						let store= Arc::clone(&store);
+						let subscribers= Arc::clone(&subscribers);
+
+						spawn(move | | Self::handleConnection(stream, store, subscribers));
+					}
+				},
+
+				ConnectionAddr::Unix(path) => {
+					let listener= UnixListener::bind(&path)
+						.expect("ERROR: binding the Unix domain socket server to given path");
+
+					for stream in listener.incoming( ) {
+						let stream= stream.expect("ERROR: parsing incoming connection stream");
+						let store= Arc::clone(&store);
+						let subscribers= Arc::clone(&subscribers);
+
+						spawn(move | | Self::handleConnection(stream, store, subscribers));
+					}
+				}
+			}
+		}
+
+		// handleConnection splits 'stream' into an owned writing half and the original reading half.
+		// The writing half is drained by a dedicated thread reading off 'mpsc' channel, so a connection
+		// subscribed to a pub/sub channel keeps receiving pushed messages while 'readCommands' is
+		// blocked waiting on the next command. The reading half runs 'readCommands' on the calling
+		// thread until the connection closes, after which this connection's own sender is dropped.
+		// The writer thread exits once every sender is gone or a write fails; the sender clones a
+		// 'SUBSCRIBE' registered stay in 'subscribers' after the connection closes, so for a
+		// subscribed connection that happens only after a 'PUBLISH' to one of its channels.
+		fn handleConnection<S: ConnectionStream>(stream: S, store: Arc<Store>, subscribers: Arc<Mutex<SubscriberRegistry>>) {
+			let mut writer= stream.tryClone( ).expect("ERROR: cloning connection stream");
+			let (sender, receiver)= mpsc::channel::<String>( );
+
+			let writerHandle= spawn(move | | {
+				for message in receiver {
+					if writer.write_all(message.as_bytes( )).is_err( ) {
+						return}
+				}
+			});
+
+			Self::readCommands(stream, store, subscribers, sender);
+
+			writerHandle.join( ).ok( );
+		}
+
+		// readCommands reads RESP frames off 'stream' into a fixed-size buffer, dispatching each
+		// complete frame as soon as it's recognized, with replies delivered to 'sender' rather than
+		// written to 'stream' directly. Bytes belonging to a frame that hasn't fully arrived yet are
+		// shifted to the front of the buffer and completed by the next read, so a frame split across
+		// reads - even mid-UTF8 - is handled correctly without growing the allocation, and a connection
+		// that sends multiple commands keeps getting replies.
+		fn readCommands<S: Read>(mut stream: S, store: Arc<Store>, subscribers: Arc<Mutex<SubscriberRegistry>>, sender: Sender<String>) {
+			let mut buffer= [0u8; BUFFER_SIZE];
+			let mut filled= 0;
+			let mut subscribedChannels= HashSet::new( );
+
+			loop {
+				let read= stream.read(&mut buffer[filled..])
+					.expect("ERROR: reading from connection");
+				if read == 0 {
+					return}
+
+				filled+= read;
+
+				loop {
+					let buffered= match str::from_utf8(&buffer[..filled]) {
+						Ok(buffered) => buffered,
+
+						// The tail of the buffer is a character split across two reads - wait for the rest.
+						Err(error) if error.error_len( ).is_none( )
+							=> str::from_utf8(&buffer[..error.valid_up_to( )]).unwrap( ),
+
+						Err(_) => panic!("ERROR: received invalid UTF-8 on connection")
+					};
+
+					match resp::decode(buffered) {
+						Ok((decodedRequest, remaining)) => {
+							Self::handleDecodedRequest(decodedRequest, &store, &subscribers, &sender, &mut subscribedChannels);
+
+							let consumed= buffered.len( ) - remaining.len( );
+							buffer.copy_within(consumed..filled, 0);
+							filled-= consumed;
+						},
+
+						Err(resp::DecodeError::Incomplete) => {
+							// The buffer is full and still doesn't hold a complete frame - reading more into
+							// an empty destination slice would block forever rather than ever returning
+							// 'Ok(0)', so there's no way to wait this out. Bail with an error instead of
+							// hanging the connection's reader thread.
+							if filled == BUFFER_SIZE {
+								let response= resp::encode(RespType::Error(
+									"ERR frame too large for the read buffer".to_string( )
+								));
+								sender.send(response).ok( );
+
+								return
+							}
+
+							break
+						},
+
+						Err(resp::DecodeError::Malformed(reason)) => {
+							let response= resp::encode(RespType::Error(format!("ERR {}", reason)));
+							sender.send(response).ok( );
+
+							return
+						}
+					}
+				}
+			}
+		}
+
+		// handleDecodedRequest takes in an already-decoded request, executes it against 'store' and
+		// 'subscribers', and delivers the RESP encoded reply (or, for 'SUBSCRIBE', replies) to 'sender'.
+		// 'subscribedChannels' is this connection's own view of what it's subscribed to, used to dedupe
+		// and size 'SUBSCRIBE' confirmations.
+		fn handleDecodedRequest(decodedRequest: RespType, store: &Store, subscribers: &Arc<Mutex<SubscriberRegistry>>, sender: &Sender<String>, subscribedChannels: &mut HashSet<String>) {
+			match decodedRequest {
 				RespType::Array(array) => {
-					let mut vector= Vec::<&'a str>::with_capacity(array.len( ));
+					let mut vector= Vec::<String>::with_capacity(array.len( ));
 
 					for respType in array {
 						match respType {
@@ -155,54 +499,308 @@ mod server {
 						}
 					}
 
-					Self::handleCommand(vector)
+					Self::handleCommand(vector, store, subscribers, sender, subscribedChannels);
 				},
 				_ => unreachable!( )
-			};
-
-			resp::encode(decodedResponse)
+			}
 		}
 
-		// handleCommand takes in a Redis command and executes it. The execution result is returned.
-		fn handleCommand(vector: Vec<&str>) -> RespType {
+		// handleCommand takes in a Redis command and executes it against 'store'/'subscribers',
+		// delivering its reply (or replies, for 'SUBSCRIBE') to 'sender'. 'subscribedChannels' is this
+		// connection's own view of what it's subscribed to.
+		fn handleCommand(vector: Vec<String>, store: &Store, subscribers: &Arc<Mutex<SubscriberRegistry>>, sender: &Sender<String>, subscribedChannels: &mut HashSet<String>) {
 			let mut iterator= vector.iter( );
 
-			let command= *iterator.next( ).unwrap( );
-			match command {
+			let command= iterator.next( ).unwrap( );
+
+			// Once this connection is subscribed to at least one channel, its replies share the same
+			// stream as the asynchronous pushes 'PUBLISH' delivers through 'sender' - running a command
+			// that replies later (like 'SET'/'GET') would race an incoming push and leave the client
+			// unable to tell which reply belongs to which request. Only 'SUBSCRIBE' and 'PING' - the
+			// commands real Redis allows in this state that this server implements - are run.
+			if !subscribedChannels.is_empty( ) && !matches!(command.as_str( ), "SUBSCRIBE" | "PING") {
+				sender.send(resp::encode(RespType::Error(format!(
+					"ERR Can't execute '{}': only SUBSCRIBE / PING are allowed in this context",
+					command.to_lowercase( )
+				)))).ok( );
+
+				return
+			}
+
+			let reply= match command.as_str( ) {
 
 				// The 'PING' command returns 'PONG' if no argument is provided, otherwise returns a copy of
 				// the argument as a bulk.
 				"PING" => {
 					let arg= iterator.next( )
-													 .map_or("PONG", |v| *v);
+													 .map_or("PONG", |v| v.as_str( ));
 
 					pingHandler(arg)
 				},
 
-				"SET" => todo!( ),
+				// The 'SET' command stores 'value' at 'key'. It supports the 'EX seconds'/'PX milliseconds'
+				// options to make the key expire after a deadline, and the 'NX'/'XX' options to only set the
+				// key if it's currently absent/present, respectively. Replies 'OK' on success, or a null
+				// reply if 'NX'/'XX' prevented the set.
+				"SET" => {
+					let key= match iterator.next( ) {
+						Some(key) => key,
 
-				"GET" => todo!( ),
+						None => {
+							sender.send(resp::encode(RespType::Error(
+								"ERR wrong number of arguments for 'set' command".to_string( )
+							))).ok( );
 
-				_ => panic!( )
-			}
+							return
+						}
+					};
+
+					let value= match iterator.next( ) {
+						Some(value) => value,
+
+						None => {
+							sender.send(resp::encode(RespType::Error(
+								"ERR wrong number of arguments for 'set' command".to_string( )
+							))).ok( );
+
+							return
+						}
+					};
+
+					let mut expiresIn= None;
+					let mut onlyIfAbsent= false;
+					let mut onlyIfPresent= false;
+
+					while let Some(option)= iterator.next( ) {
+						match option.to_uppercase( ).as_str( ) {
+							"EX" => {
+								let seconds= match iterator.next( ).and_then(|v| v.parse::<u64>( ).ok( )) {
+									Some(seconds) => seconds,
+
+									None => {
+										sender.send(resp::encode(RespType::Error(
+											"ERR value is not an integer or out of range".to_string( )
+										))).ok( );
+
+										return
+									}
+								};
+
+								expiresIn= Some(Duration::from_secs(seconds));
+							},
+
+							"PX" => {
+								let milliseconds= match iterator.next( ).and_then(|v| v.parse::<u64>( ).ok( )) {
+									Some(milliseconds) => milliseconds,
+
+									None => {
+										sender.send(resp::encode(RespType::Error(
+											"ERR value is not an integer or out of range".to_string( )
+										))).ok( );
+
+										return
+									}
+								};
+
+								expiresIn= Some(Duration::from_millis(milliseconds));
+							},
+
+							"NX" => onlyIfAbsent= true,
+
+							"XX" => onlyIfPresent= true,
+
+							_ => {
+								sender.send(resp::encode(RespType::Error(
+									format!("ERR unknown option '{}' for 'set' command", option)
+								))).ok( );
+
+								return
+							}
+						}
+					}
+
+					if store.set(key, value, expiresIn, onlyIfAbsent, onlyIfPresent) {
+						RespType::SimpleString("OK".to_string( ))
+					} else {
+						RespType::Null
+					}
+				},
+
+				// The 'GET' command returns the value stored at 'key' as a bulk string, or a null reply if
+				// the key is absent or has expired.
+				"GET" => {
+					let key= match iterator.next( ) {
+						Some(key) => key,
+
+						None => {
+							sender.send(resp::encode(RespType::Error(
+								"ERR wrong number of arguments for 'get' command".to_string( )
+							))).ok( );
+
+							return
+						}
+					};
+
+					match store.get(key) {
+						Some(value) => RespType::BulkString(value),
+
+						None => RespType::Null
+					}
+				},
+
+				// The 'SUBSCRIBE' command registers this connection's sender against each given channel -
+				// unless it's already subscribed to that channel, which is tracked via
+				// 'subscribedChannels' so re-subscribing never double-registers a sender and so a
+				// later 'PUBLISH' can't deliver the same message to this connection twice. Replies with
+				// one subscribe confirmation array (`subscribe`, channel, this connection's total
+				// subscription count) per channel, delivered directly since there's more than one reply.
+				"SUBSCRIBE" => {
+					let channels: Vec<&String>= iterator.collect( );
+					if channels.is_empty( ) {
+						sender.send(resp::encode(RespType::Error(
+							"ERR wrong number of arguments for 'subscribe' command".to_string( )
+						))).ok( );
+
+						return
+					}
+
+					let mut registry= subscribers.lock( ).unwrap( );
+
+					for channel in channels {
+						if subscribedChannels.insert(channel.clone( )) {
+							registry.subscribe(channel, sender.clone( ));
+						}
+
+						let confirmation= RespType::Array(vec![
+							RespType::BulkString("subscribe".to_string( )),
+							RespType::BulkString(channel.clone( )),
+							RespType::Integer(subscribedChannels.len( ) as i64)
+						]);
+
+						sender.send(resp::encode(confirmation)).ok( );
+					}
+
+					return
+				},
+
+				// The 'PUBLISH' command delivers a 3-element push array (`message`, channel, payload) to
+				// every current subscriber of the given channel, replying with the number of receivers.
+				"PUBLISH" => {
+					let channel= match iterator.next( ) {
+						Some(channel) => channel,
+
+						None => {
+							sender.send(resp::encode(RespType::Error(
+								"ERR wrong number of arguments for 'publish' command".to_string( )
+							))).ok( );
+
+							return
+						}
+					};
+
+					let payload= match iterator.next( ) {
+						Some(payload) => payload,
+
+						None => {
+							sender.send(resp::encode(RespType::Error(
+								"ERR wrong number of arguments for 'publish' command".to_string( )
+							))).ok( );
+
+							return
+						}
+					};
+
+					let pushedMessage= resp::encode(RespType::Array(vec![
+						RespType::BulkString("message".to_string( )),
+						RespType::BulkString(channel.clone( )),
+						RespType::BulkString(payload.clone( ))
+					]));
+
+					let receivers= subscribers.lock( ).unwrap( ).publish(channel, pushedMessage);
+
+					RespType::Integer(receivers)
+				},
+
+				// An unrecognized command gets a RESP error reply rather than crashing the connection's
+				// thread.
+				unknown => RespType::Error(format!("ERR unknown command '{}'", unknown))
+			};
+
+			sender.send(resp::encode(reply)).ok( );
 		}
 	}
 }
 
 mod client {
-	use std::{io::{stdin, stdout, Write}, net::{TcpStream, SocketAddr}, time::Duration};
+	use std::{
+		io::{self, stdin, stdout, Read, Write},
+		net::{TcpStream, ToSocketAddrs},
+		os::unix::net::UnixStream,
+		time::Duration
+	};
+	use crate::connection::{self, ConnectionAddr};
+	use crate::resp::{self, RespType};
+
+	// Connection is the transport a 'Client' dials over - either a TCP socket or a Unix domain
+	// socket - so the rest of 'Client' can talk to either uniformly.
+	enum Connection {
+		Tcp(TcpStream),
+		Unix(UnixStream)
+	}
+
+	impl Read for Connection {
+		fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+			match self {
+				Connection::Tcp(stream) => stream.read(buffer),
+				Connection::Unix(stream) => stream.read(buffer)
+			}
+		}
+	}
+
+	impl Write for Connection {
+		fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+			match self {
+				Connection::Tcp(stream) => stream.write(buffer),
+				Connection::Unix(stream) => stream.write(buffer)
+			}
+		}
+
+		fn flush(&mut self) -> io::Result<( )> {
+			match self {
+				Connection::Tcp(stream) => stream.flush( ),
+				Connection::Unix(stream) => stream.flush( )
+			}
+		}
+	}
 
 	pub struct Client {
-		connection: TcpStream
+		connection: Connection
 	}
 
 	impl Client {
 		pub fn new(serverAddress: &str) -> Self {
-			let serverAddress= serverAddress.parse::<SocketAddr>( )
-																			.expect("ERROR: Invalid Redis server address received");
+			let connection= match connection::parseConnectionInfo(serverAddress) {
+				ConnectionAddr::Tcp(host, port) => {
+					// Resolve 'host' the same way 'TcpListener::bind' does on the server side, so a
+					// hostname (e.g. from 'redis://localhost:6379') works here too, not just an IP literal.
+					let socketAddresses= (host.as_str( ), port).to_socket_addrs( )
+						.expect("ERROR: Invalid Redis server address received");
+
+					let stream= socketAddresses
+						.filter_map(|socketAddress| TcpStream::connect_timeout(&socketAddress, Duration::from_secs(3)).ok( ))
+						.next( )
+						.expect("ERROR: Couldn't connect to the Redis server");
+
+					Connection::Tcp(stream)
+				},
 
-			let connection= TcpStream::connect_timeout(&serverAddress, Duration::from_secs(3))
-																 .expect("ERROR: Couldn't connect to the Redis server");
+				ConnectionAddr::Unix(path) => {
+					let stream= UnixStream::connect(&path)
+						.expect("ERROR: Couldn't connect to the Redis server");
+
+					Connection::Unix(stream)
+				}
+			};
 
 			Client { connection }
 		}
@@ -214,17 +812,124 @@ mod client {
 				stdout( ).flush( ).unwrap( );
 
 				let mut input= String::new( );
-				stdin( ).read_line(&mut input).unwrap( );
+				if stdin( ).read_line(&mut input).unwrap( ) == 0 {
+					// EOF (e.g. Ctrl+D) - exit the REPL.
+					println!( );
+					return}
+
+				let arguments= Self::tokenize(input.trim( ));
+				if arguments.is_empty( ) {
+					continue}
+
+				if arguments[0].eq_ignore_ascii_case("quit") {
+					return}
+
+				let request= RespType::Array(arguments.into_iter( ).map(RespType::BulkString).collect( ));
+				self.connection.write_all(resp::encode(request).as_bytes( )).unwrap( );
+
+				let response= self.readResponse( );
+				Self::printResponse(&response, 0);
+			}
+		}
+
+		// tokenize splits a REPL input line into arguments, honouring double-quoted arguments so a
+		// value containing spaces can be passed as a single argument.
+		fn tokenize(input: &str) -> Vec<String> {
+			let mut arguments= Vec::new( );
+			let mut current= String::new( );
+			let mut insideQuotes= false;
+
+			for c in input.chars( ) {
+				match c {
+					'"' => insideQuotes= !insideQuotes,
+
+					c if c.is_whitespace( ) && !insideQuotes => {
+						if !current.is_empty( ) {
+							arguments.push(std::mem::take(&mut current));}
+					},
+
+					c => current.push(c)
+				}
+			}
+
+			if !current.is_empty( ) {
+				arguments.push(current);}
 
-				todo!( )
+			arguments
+		}
+
+		// readResponse reads one RESP encoded reply off 'self.connection'.
+		fn readResponse(&mut self) -> RespType {
+			let mut buffer= Vec::new( );
+			let mut chunk= [0u8; 4096];
+
+			loop {
+				let read= self.connection.read(&mut chunk)
+					.expect("ERROR: reading the server's reply");
+				if read == 0 {
+					panic!("ERROR: connection to the Redis server closed")}
+
+				buffer.extend_from_slice(&chunk[..read]);
+
+				let buffered= std::str::from_utf8(&buffer)
+					.expect("ERROR: received invalid UTF-8 from the server");
+
+				match resp::decode(buffered) {
+					Ok((decodedResponse, _)) => return decodedResponse,
+
+					Err(resp::DecodeError::Incomplete) => continue,
+
+					Err(resp::DecodeError::Malformed(reason))
+						=> panic!("ERROR: malformed RESP reply received: {}", reason)
+				}
+			}
+		}
+
+		// printResponse pretty-prints a decoded RESP reply the way redis-cli does, indenting nested
+		// array elements by 'depth'.
+		fn printResponse(response: &RespType, depth: usize) {
+			match response {
+				RespType::SimpleString(s) => println!("{}", s),
+
+				RespType::BulkString(s) => println!("\"{}\"", s),
+
+				RespType::Integer(i) => println!("(integer) {}", i),
+
+				RespType::Error(message) => println!("(error) {}", message),
+
+				RespType::Null | RespType::NullArray => println!("(nil)"),
+
+				RespType::Array(array) => {
+					if array.is_empty( ) {
+						println!("(empty array)");
+						return}
+
+					for (i, item) in array.iter( ).enumerate( ) {
+						print!("{}{}) ", "\t".repeat(depth), i + 1);
+						Self::printResponse(item, depth + 1);
+					}
+				}
 			}
 		}
 	}
 }
 
+// Running with no arguments (or a bare address) starts the server on that address, defaulting to
+// '127.0.0.1:6379'. Running with '--cli [address]' instead connects a 'Client' to that address and
+// starts its interactive REPL.
 fn main( ) {
-	let request= "*1\r\n$4\r\nPING\r\n";
+	let mut arguments= std::env::args( ).skip(1);
+
+	match arguments.next( ).as_deref( ) {
+		Some("--cli") => {
+			let serverAddress= arguments.next( ).unwrap_or_else(|| "127.0.0.1:6379".to_string( ));
+
+			let mut client= client::Client::new(&serverAddress);
+			client.startRepl( );
+		},
 
-	let result= server::TcpServer::handleRequest(request);
-	println!("{}", result);
-}
\ No newline at end of file
+		Some(address) => server::TcpServer::new(address),
+
+		None => server::TcpServer::new("127.0.0.1:6379")
+	}
+}